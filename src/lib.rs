@@ -0,0 +1,647 @@
+use std::collections::HashMap;
+
+// 2つの文字列が先頭から一致しているバイト数を返す
+// a, bの両方に含まれる共通の文字境界でしか一致判定を打ち切らないため，
+// 戻り値は常にa, b双方にとって有効なスライス位置になる
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    let mut len = 0;
+    for (ca, cb) in a.chars().zip(b.chars()) {
+        if ca != cb {
+            break;
+        }
+        len += ca.len_utf8();
+    }
+    len
+}
+
+// Trieを構成するノードを表現するstruct
+// 親から自身に至るまでの文字列（ラベル）と，0コ以上の子Nodeを持つことができる
+// 終端（ある単語の末尾）に到達したノードは値Vを保持する
+//
+// Patricia木として，分岐しない文字の連なりは1つのラベルにまとめて1ノードに圧縮される
+// e.g. "text"単独なら [text] というラベルを持つノード1つだけになる
+//
+// 外部に公開されるのはNodeRef経由でのみで，フィールドそのものは非公開．
+// label()/value()アクセサを通じて読み取る
+pub struct Node<V> {
+    // 親から自身までのエッジに対応する文字列
+    label: String,
+    // このノードで終端する場合に格納される値
+    // 終端でなければNone
+    value: Option<V>,
+    // ノードにぶら下がっている子ノードを，そのラベルの先頭文字をキーにして保持する
+    // HashMapにすることで同じ先頭文字の子ノードを線形探索する必要がなくなる
+    children: HashMap<char, Node<V>>,
+}
+
+impl<V> Node<V> {
+    // 子ノードを持たない終端のNodeを生成する
+    fn new_leaf(label: String, value: V) -> Node<V> {
+        return Node {
+            label: label,
+            value: Some(value),
+            children: HashMap::new(),
+        };
+    }
+
+    // Nodeの識別子を文字列として返す
+    // DOT言語を吐き出す際のノード名として使う
+    fn id(&self) -> String {
+        // idとしてポインタのアドレスを利用する
+        let addr = (self as *const Node<V>) as usize;
+        return format!("node_{}", addr);
+    }
+
+    // 親から自身までのエッジに対応する文字列を返す
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    // このノードで終端する場合に格納されている値への参照を返す
+    // 終端でなければNone
+    pub fn value(&self) -> Option<&V> {
+        self.value.as_ref()
+    }
+
+    // restをこのノードのラベルに沿って挿入する
+    // restの先頭はこのノードのラベルと少なくとも1文字は共有している前提
+    // ラベルの途中でrestと食い違う場合は，共通部分で自身を分割してから続ける
+    fn insert(&mut self, rest: &str, value: V) -> bool {
+        let cp = common_prefix_len(&self.label, rest);
+        if cp < self.label.len() {
+            // ラベルの途中でrestと分岐する -> 共通部分を切り出して自身を2つに割る
+            let common = self.label[..cp].to_string();
+            let old_suffix = self.label[cp..].to_string();
+            let old_first = old_suffix.chars().next().unwrap();
+
+            let old_node = Node {
+                label: old_suffix,
+                value: self.value.take(),
+                children: std::mem::take(&mut self.children),
+            };
+            self.label = common;
+            self.children.insert(old_first, old_node);
+        }
+
+        let remaining = &rest[cp..];
+        if remaining.is_empty() {
+            let is_new = self.value.is_none();
+            self.value = Some(value);
+            return is_new;
+        }
+
+        let c = remaining.chars().next().unwrap();
+        match self.children.get_mut(&c) {
+            Some(child) => child.insert(remaining, value),
+            None => {
+                self.children.insert(c, Node::new_leaf(remaining.to_string(), value));
+                true
+            }
+        }
+    }
+
+    // restをこのノードのラベルに沿って辿り，辿り切った先のノードを返す
+    // ラベルの途中で食い違う，または子ノードが見つからなければNone
+    fn find(&self, rest: &str) -> Option<&Node<V>> {
+        let cp = common_prefix_len(&self.label, rest);
+        if cp < self.label.len() {
+            return None;
+        }
+        let remaining = &rest[cp..];
+        if remaining.is_empty() {
+            return Some(self);
+        }
+        let c = remaining.chars().next().unwrap();
+        self.children.get(&c).and_then(|n| n.find(remaining))
+    }
+
+    // restをこのノードのラベルに沿って辿ってキーを削除する
+    // 辿り切った先のノードの値をremovedに取り出し，そのノードが非終端かつ子を持たなくなった
+    // （＝親から枝刈りしてよい）場合にtrueを返す
+    fn remove(&mut self, rest: &str, removed: &mut Option<V>) -> bool {
+        let cp = common_prefix_len(&self.label, rest);
+        if cp < self.label.len() {
+            return false; // キーが存在しないので何もしない
+        }
+        let remaining = &rest[cp..];
+        if remaining.is_empty() {
+            *removed = self.value.take();
+            self.merge_if_redundant();
+            return self.value.is_none() && self.children.is_empty();
+        }
+        let c = remaining.chars().next().unwrap();
+        let prune_child = match self.children.get_mut(&c) {
+            Some(child) => child.remove(remaining, removed),
+            None => return false,
+        };
+        if prune_child {
+            self.children.remove(&c);
+        }
+        self.merge_if_redundant();
+        self.value.is_none() && self.children.is_empty()
+    }
+
+    // 非終端かつ子が1つだけになったノードを，その子のラベルを連結して1エッジに戻す
+    // （挿入時に分岐点で行う分割の逆操作）．条件を満たさなければ何もしない
+    fn merge_if_redundant(&mut self) {
+        if self.value.is_none() && self.children.len() == 1 {
+            let (_, child) = self.children.drain().next().unwrap();
+            self.label.push_str(&child.label);
+            self.value = child.value;
+            self.children = child.children;
+        }
+    }
+
+    // 自身と配下の部分木に格納されている全てのキーをfに渡す
+    // bufには呼び出し時点で自身のlabelまでが積まれている前提で，子を辿る際にlabelを push/truncate して使い回す
+    fn foreach(&self, buf: &mut String, f: &dyn Fn(&str, &V)) {
+        if let Some(v) = &self.value {
+            f(buf, v);
+        }
+        for child in self.children.values() {
+            let mark = buf.len();
+            buf.push_str(&child.label);
+            child.foreach(buf, f);
+            buf.truncate(mark);
+        }
+    }
+
+    // NodeをDOT言語として出力する
+    // グラフのノードの定義とエッジの定義が同時に出力される
+    fn print_dot(&self) {
+        // 終端ノードは二重丸で表示して区別する
+        let shape = if self.value.is_some() {
+            "doublecircle"
+        } else {
+            "plain"
+        };
+        println!(
+            "{} [label=\"{}\",shape={}];",
+            self.id(),
+            self.label,
+            shape
+        );
+        for nn in self.children.values() {
+            println!("{} -> {};", self.id(), &nn.id());
+            // 再帰的に子ノードについても呼び出すことでノードにぶら下がった全てのノードが出力される
+            nn.print_dot();
+        }
+    }
+}
+
+// node_at_prefixで返される，辿り着いたノードへのハンドル
+// Nodeは親へのポインタを持っていないので，根から辿り着いたノードまでに
+// 訪れたノードの参照を順番に保持しておき，ancestors()ではそれを逆順に辿ることで
+// 「辿り着いたノードから根に向かって祖先をたどる」動きを実現する
+pub struct NodeRef<'a, V> {
+    // path[0]が根から最初に下った子，path.last()が辿り着いたノード
+    path: Vec<&'a Node<V>>,
+}
+
+impl<'a, V> NodeRef<'a, V> {
+    // prefixを辿り切った先のノードそのものを返す
+    pub fn node(&self) -> &'a Node<V> {
+        self.path[self.path.len() - 1]
+    }
+
+    // 辿り着いたノードから根に向かって祖先を辿るイテレータ（自身を含む）
+    // スペルチェックや最長一致するプレフィックス探索はこれを使って実装できる
+    pub fn ancestors(&self) -> impl Iterator<Item = &'a Node<V>> + '_ {
+        self.path.iter().rev().copied()
+    }
+}
+
+// トライ木を表現するstruct
+// 別の文字列から始まる複数のNodeを束ねただけである
+// Vに終端ノードの値の型を指定することで，文字列をキーとした連想配列として使える
+pub struct Trie<V> {
+    children: HashMap<char, Node<V>>,
+    // 格納されているキーの総数
+    len: usize,
+}
+
+impl<V> Default for Trie<V> {
+    fn default() -> Self {
+        Trie::new()
+    }
+}
+
+impl<V> Trie<V> {
+    // 空のTrieを生成する
+    pub fn new() -> Trie<V> {
+        return Trie {
+            children: HashMap::new(),
+            len: 0,
+        };
+    }
+
+    // keyをTrieに挿入し，対応する値としてvalueを格納する
+    // 既に同じkeyが存在していた場合は値が上書きされる
+    pub fn insert(&mut self, key: &str, value: V) {
+        if key.is_empty() {
+            return; // 空文字列は無視する
+        }
+        let c = key.chars().next().unwrap();
+        let is_new = match self.children.get_mut(&c) {
+            Some(child) => child.insert(key, value),
+            None => {
+                self.children.insert(c, Node::new_leaf(key.to_string(), value));
+                true
+            }
+        };
+        if is_new {
+            self.len += 1;
+        }
+    }
+
+    // keyに対応するノードを探索する
+    fn find_node(&self, key: &str) -> Option<&Node<V>> {
+        if key.is_empty() {
+            return None;
+        }
+        let c = key.chars().next().unwrap();
+        self.children.get(&c).and_then(|n| n.find(key))
+    }
+
+    // prefixを根から辿り，辿り着いたノードとその経路をNodeRefとして返す
+    // 経路の途中でラベルと食い違う，または子ノードが見つからなければNone
+    pub fn node_at_prefix(&self, prefix: &str) -> Option<NodeRef<'_, V>> {
+        if prefix.is_empty() {
+            return None;
+        }
+        let first = prefix.chars().next().unwrap();
+        let mut node = self.children.get(&first)?;
+        let mut path = vec![node];
+        let mut rest = prefix;
+        loop {
+            let cp = common_prefix_len(&node.label, rest);
+            if cp < node.label.len() && cp < rest.len() {
+                return None; // ラベルの途中でprefixと食い違う
+            }
+            if cp == rest.len() {
+                return Some(NodeRef { path });
+            }
+            rest = &rest[cp..];
+            let c = rest.chars().next().unwrap();
+            node = node.children.get(&c)?;
+            path.push(node);
+        }
+    }
+
+    // keyが格納されているかどうかを返す
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.find_node(key)
+            .map(|n| n.value.is_some())
+            .unwrap_or(false)
+    }
+
+    // keyに対応する値への参照を返す
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.find_node(key).and_then(|n| n.value.as_ref())
+    }
+
+    // 格納されているキーの総数を返す
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    // Trieが空かどうかを返す
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Trieの中身を空にする
+    pub fn clear(&mut self) {
+        self.children.clear();
+        self.len = 0;
+    }
+
+    // keyを削除し，それまで格納されていた値を返す
+    // keyが存在しなければNoneを返し，Trieには一切手を加えない
+    // 削除によって非終端かつ子を持たなくなったノードは，根に向かって連鎖的に刈り取られる
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        if key.is_empty() {
+            return None; // 空文字列は格納され得ないので何もしない
+        }
+        let c = key.chars().next().unwrap();
+        let mut removed = None;
+        let prune = match self.children.get_mut(&c) {
+            Some(child) => child.remove(key, &mut removed),
+            None => false,
+        };
+        if prune {
+            self.children.remove(&c);
+        }
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    // 格納されている全てのキーと値の組をfに渡す
+    // キーの再構築には使い回す1つのStringバッファを使うことで，ノード毎のアロケーションを避ける
+    pub fn foreach(&self, f: &dyn Fn(&str, &V)) {
+        let mut buf = String::new();
+        for child in self.children.values() {
+            buf.push_str(&child.label);
+            child.foreach(&mut buf, f);
+            buf.truncate(0);
+        }
+    }
+
+    // seqをプレフィックスとして持つ全てのキーと値の組をfに渡す
+    // seq自体がキーとして格納されていればそれも含まれる
+    pub fn common_prefix(&self, seq: &str, f: &dyn Fn(&str, &V)) {
+        let mut buf = String::new();
+        if seq.is_empty() {
+            // 空のseqはTrie全体が対象になる
+            self.foreach(f);
+            return;
+        }
+        let first = seq.chars().next().unwrap();
+        let mut node = match self.children.get(&first) {
+            Some(n) => n,
+            None => return, // seqに一致するノードが存在しない
+        };
+        let mut rest = seq;
+        loop {
+            let cp = common_prefix_len(&node.label, rest);
+            if cp < node.label.len() && cp < rest.len() {
+                // ラベルの途中でseqと食い違う -> 一致するキーは存在しない
+                return;
+            }
+            buf.push_str(&node.label);
+            if cp == rest.len() {
+                // seqをこのノードのラベル内で使い切った -> 以下の部分木が全て対象
+                node.foreach(&mut buf, f);
+                return;
+            }
+            // ラベルを全て消費してもまだseqが残っている -> 子を辿る
+            rest = &rest[cp..];
+            let c = rest.chars().next().unwrap();
+            node = match node.children.get(&c) {
+                Some(n) => n,
+                None => return,
+            };
+        }
+    }
+
+    // TrieをDOT言語で出力する
+    // ヘッダ・フッタを出力し，あとはNode::print_dotに任せる
+    pub fn print_dot(&self) {
+        println!("digraph {{\nrankdir=LR;");
+        for n in self.children.values() {
+            n.print_dot();
+        }
+        println!("}}");
+    }
+}
+
+// TEST SECTION
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    #[test]
+    fn it_works() {
+        assert_eq!(2 + 2, 4);
+    }
+
+    #[test]
+    fn can_insert_and_get() {
+        let mut t = Trie::new();
+        t.insert("win", 1);
+        assert_eq!(t.get("win"), Some(&1));
+        assert_eq!(t.get("wi"), None);
+        assert_eq!(t.get("winner"), None);
+    }
+
+    #[test]
+    fn contains_key_reflects_terminal_nodes_only() {
+        let mut t = Trie::new();
+        t.insert("win", 1);
+        assert!(t.contains_key("win"));
+        assert!(!t.contains_key("wi")); // "wi"は圧縮ラベル"win"の途中にあるだけで，終端ノードではない
+    }
+
+    #[test]
+    fn len_counts_distinct_keys_and_ignores_reinsertion() {
+        let mut t = Trie::new();
+        assert!(t.is_empty());
+        t.insert("win", 1);
+        t.insert("won", 2);
+        assert_eq!(t.len(), 2);
+        t.insert("win", 3); // 上書きなのでlenは増えない
+        assert_eq!(t.len(), 2);
+        assert_eq!(t.get("win"), Some(&3));
+    }
+
+    #[test]
+    fn clear_empties_the_trie() {
+        let mut t = Trie::new();
+        t.insert("win", 1);
+        t.clear();
+        assert!(t.is_empty());
+        assert_eq!(t.get("win"), None);
+    }
+
+    #[test]
+    fn sibling_keys_sharing_a_prefix_do_not_collide() {
+        // winとwonはwの子ノードの時点で分岐するが，HashMap化した子ノードが
+        // それぞれ独立したエントリとして保持されることを確認する
+        let mut t = Trie::new();
+        t.insert("win", 1);
+        t.insert("won", 2);
+        assert_eq!(t.get("win"), Some(&1));
+        assert_eq!(t.get("won"), Some(&2));
+    }
+
+    #[test]
+    fn foreach_visits_every_key_with_its_value() {
+        let mut t = Trie::new();
+        t.insert("win", 1);
+        t.insert("won", 2);
+        t.insert("wonder", 3);
+
+        let seen: RefCell<Vec<(String, i32)>> = RefCell::new(vec![]);
+        t.foreach(&|k, v| seen.borrow_mut().push((k.to_string(), *v)));
+        let mut seen = seen.into_inner();
+        seen.sort();
+
+        assert_eq!(
+            seen,
+            vec![
+                ("win".to_string(), 1),
+                ("won".to_string(), 2),
+                ("wonder".to_string(), 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn common_prefix_only_yields_keys_under_the_given_prefix() {
+        let mut t = Trie::new();
+        t.insert("win", 1);
+        t.insert("won", 2);
+        t.insert("wonder", 3);
+
+        let seen: RefCell<Vec<String>> = RefCell::new(vec![]);
+        t.common_prefix("won", &|k, _| seen.borrow_mut().push(k.to_string()));
+        let mut seen = seen.into_inner();
+        seen.sort();
+
+        assert_eq!(seen, vec!["won".to_string(), "wonder".to_string()]);
+    }
+
+    #[test]
+    fn common_prefix_with_no_match_yields_nothing() {
+        let mut t = Trie::new();
+        t.insert("win", 1);
+
+        let seen: RefCell<Vec<String>> = RefCell::new(vec![]);
+        t.common_prefix("xyz", &|k, _| seen.borrow_mut().push(k.to_string()));
+        assert!(seen.into_inner().is_empty());
+    }
+
+    #[test]
+    fn remove_returns_the_value_and_forgets_the_key() {
+        let mut t = Trie::new();
+        t.insert("win", 1);
+        assert_eq!(t.remove("win"), Some(1));
+        assert_eq!(t.get("win"), None);
+        assert!(t.is_empty());
+    }
+
+    #[test]
+    fn remove_of_missing_key_leaves_the_trie_untouched() {
+        let mut t = Trie::new();
+        t.insert("win", 1);
+        assert_eq!(t.remove("wine"), None);
+        assert_eq!(t.remove("wi"), None);
+        assert_eq!(t.get("win"), Some(&1));
+        assert_eq!(t.len(), 1);
+    }
+
+    #[test]
+    fn remove_keeps_a_longer_key_that_shares_the_removed_prefix() {
+        let mut t = Trie::new();
+        t.insert("win", 1);
+        t.insert("winner", 2);
+        assert_eq!(t.remove("win"), Some(1));
+        assert_eq!(t.get("win"), None);
+        assert_eq!(t.get("winner"), Some(&2)); // winnerは生き残る
+        assert_eq!(t.len(), 1);
+    }
+
+    #[test]
+    fn remove_prunes_now_dead_branches_but_not_shared_ones() {
+        let mut t = Trie::new();
+        t.insert("win", 1);
+        t.insert("won", 2);
+        assert_eq!(t.remove("win"), Some(1));
+        // "win"しか使っていなかった枝は刈り取られる
+        assert!(t.find_node("wi").is_none());
+        // "w"はwonとも共有しているので刈り取られずに残る
+        assert_eq!(t.get("won"), Some(&2));
+    }
+
+    #[test]
+    fn remove_re_merges_a_sibling_left_as_the_only_child() {
+        // winを消すとwon側の枝だけが残るが，中間ノード"w"は単独の子に戻るので
+        // 分割前と同じ「1エッジ1ノード」に圧縮され直すべきである
+        let mut t = Trie::new();
+        t.insert("win", 1);
+        t.insert("won", 2);
+        assert_eq!(t.remove("win"), Some(1));
+
+        assert_eq!(t.children.len(), 1);
+        let node = t.children.get(&'w').unwrap();
+        assert_eq!(node.label, "won");
+        assert_eq!(node.value, Some(2));
+        assert!(node.children.is_empty());
+    }
+
+    #[test]
+    fn remove_re_merges_a_node_left_with_a_single_child_after_its_own_value_is_cleared() {
+        // "winner"を挿入すると"win"ノードの下に子"ner"がぶら下がる形になるが，
+        // "win"自身を消した後は非終端かつ子が1つだけのノードが残るので，
+        // その子と連結して単独の"winner"エッジに戻るべきである
+        let mut t = Trie::new();
+        t.insert("win", 1);
+        t.insert("winner", 2);
+        assert_eq!(t.remove("win"), Some(1));
+
+        assert_eq!(t.children.len(), 1);
+        let node = t.children.get(&'w').unwrap();
+        assert_eq!(node.label, "winner");
+        assert_eq!(node.value, Some(2));
+        assert!(node.children.is_empty());
+
+        assert_eq!(t.get("winner"), Some(&2));
+        assert_eq!(t.get("win"), None);
+    }
+
+    #[test]
+    fn a_lone_word_is_stored_as_a_single_compressed_edge() {
+        // "text"単独なら，4つのノードに分解されず[t]->[e]->[x]->[t]のように
+        // 1エッジ1ノードへ圧縮され，ルート直下の1ノードだけで表現される
+        let mut t = Trie::new();
+        t.insert("text", 1);
+        assert_eq!(t.children.len(), 1);
+        let node = t.children.values().next().unwrap();
+        assert_eq!(node.label, "text");
+        assert!(node.children.is_empty());
+    }
+
+    #[test]
+    fn diverging_insert_splits_the_compressed_edge_at_the_common_prefix() {
+        // "text"と"team"は"te"までが共通なので，ラベル"te"の中間ノードへ分割され，
+        // その下に"xt"と"am"の2つの子ノードがぶら下がる
+        let mut t = Trie::new();
+        t.insert("text", 1);
+        t.insert("team", 2);
+
+        assert_eq!(t.children.len(), 1);
+        let mid = t.children.values().next().unwrap();
+        assert_eq!(mid.label, "te");
+        assert!(mid.value.is_none());
+        assert_eq!(mid.children.len(), 2);
+
+        assert_eq!(t.get("text"), Some(&1));
+        assert_eq!(t.get("team"), Some(&2));
+        assert_eq!(t.get("te"), None); // "te"自体はキーとして挿入していない
+    }
+
+    #[test]
+    fn node_at_prefix_reaches_a_node_mid_label() {
+        // "te"は"text"の圧縮ラベルの途中にしか存在しないが，それでもノードには辿り着ける
+        let mut t = Trie::new();
+        t.insert("text", 1);
+        let found = t.node_at_prefix("te").unwrap();
+        assert_eq!(found.node().label(), "text");
+        assert_eq!(found.node().value(), Some(&1));
+    }
+
+    #[test]
+    fn node_at_prefix_returns_none_when_the_path_breaks() {
+        let mut t = Trie::new();
+        t.insert("text", 1);
+        assert!(t.node_at_prefix("tax").is_none());
+        assert!(t.node_at_prefix("").is_none());
+    }
+
+    #[test]
+    fn ancestors_finds_the_longest_stored_prefix_of_the_input() {
+        // "tex"自体はキーではないが，祖先を遡ると"te"がキーとして見つかる
+        let mut t = Trie::new();
+        t.insert("te", 3);
+        t.insert("text", 1);
+        t.insert("texas", 2);
+
+        let found = t.node_at_prefix("tex").unwrap();
+        assert!(found.node().value().is_none()); // "tex"自体は格納されていない
+
+        let longest_match = found.ancestors().find(|n| n.value().is_some()).unwrap();
+        assert_eq!(longest_match.value(), Some(&3)); // "te"
+    }
+}